@@ -0,0 +1,25 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+pub mod os;
+
+use crate::state::ThreadSafeState;
+use deno::Isolate;
+
+/// Registers every op exposed by the `os` module onto the isolate's JSON
+/// dispatch table. `op_start`, `op_set_env` and the home-directory op (now
+/// `op_dir`) used to speak the legacy flatbuffer protocol and were wired up
+/// separately; now that they've migrated to JSON dispatch, they're
+/// registered here like the rest.
+pub fn init(i: &mut Isolate, state: &ThreadSafeState) {
+  i.register_op("start", state.stateful_json_op(os::op_start));
+  i.register_op("dir", state.stateful_json_op(os::op_dir));
+  i.register_op("exec_path", state.stateful_json_op(os::op_exec_path));
+  i.register_op("set_env", state.stateful_json_op(os::op_set_env));
+  i.register_op("env", state.stateful_json_op(os::op_env));
+  i.register_op("get_env", state.stateful_json_op(os::op_get_env));
+  i.register_op("delete_env", state.stateful_json_op(os::op_delete_env));
+  i.register_op("os_info", state.stateful_json_op(os::op_os_info));
+  i.register_op("loadavg", state.stateful_json_op(os::op_loadavg));
+  i.register_op("os_uptime", state.stateful_json_op(os::op_os_uptime));
+  i.register_op("exit", state.stateful_json_op(os::op_exit));
+  i.register_op("is_tty", state.stateful_json_op(os::op_is_tty));
+}
@@ -1,113 +1,74 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-use super::dispatch_flatbuffers::serialize_response;
 use super::dispatch_json::{Deserialize, JsonOp, Value};
 use super::utils::*;
 use crate::ansi;
 use crate::fs as deno_fs;
-use crate::msg;
 use crate::state::ThreadSafeState;
 use crate::version;
 use atty;
 use deno::*;
-use flatbuffers::FlatBufferBuilder;
 use log;
+use num_cpus;
 use std::collections::HashMap;
 use std::env;
+use sys_info;
 use url::Url;
 
 pub fn op_start(
   state: &ThreadSafeState,
-  base: &msg::Base<'_>,
-  data: Option<PinnedBuf>,
-) -> CliOpResult {
-  assert!(data.is_none());
-  let mut builder = FlatBufferBuilder::new();
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let cwd_path = env::current_dir().unwrap();
+  let cwd = deno_fs::normalize_path(cwd_path.as_ref());
 
-  let state = state;
-  let argv = state.argv.iter().map(String::as_str).collect::<Vec<_>>();
-  let argv_off = builder.create_vector_of_strings(argv.as_slice());
+  Ok(JsonOp::Sync(json!({
+    "cwd": cwd,
+    "pid": std::process::id(),
+    "argv": state.argv,
+    "mainModule": state.main_module().map(|m| m.to_string()),
+    "debugFlag": state.flags.log_level.map_or(false, |l| l == log::Level::Debug),
+    "versionFlag": state.flags.version,
+    "v8Version": version::v8(),
+    "denoVersion": version::DENO,
+    "noColor": !ansi::use_color(),
+    "xevalDelim": state.flags.xeval_delim,
+  })))
+}
 
-  let cwd_path = env::current_dir().unwrap();
-  let cwd_off =
-    builder.create_string(deno_fs::normalize_path(cwd_path.as_ref()).as_ref());
-
-  let v8_version = version::v8();
-  let v8_version_off = builder.create_string(v8_version);
-
-  let deno_version = version::DENO;
-  let deno_version_off = builder.create_string(deno_version);
-
-  let main_module = state
-    .main_module()
-    .map(|m| builder.create_string(&m.to_string()));
-
-  let xeval_delim = state
-    .flags
-    .xeval_delim
-    .clone()
-    .map(|m| builder.create_string(&m));
-
-  let debug_flag = state
-    .flags
-    .log_level
-    .map_or(false, |l| l == log::Level::Debug);
-
-  let inner = msg::StartRes::create(
-    &mut builder,
-    &msg::StartResArgs {
-      cwd: Some(cwd_off),
-      pid: std::process::id(),
-      argv: Some(argv_off),
-      main_module,
-      debug_flag,
-      version_flag: state.flags.version,
-      v8_version: Some(v8_version_off),
-      deno_version: Some(deno_version_off),
-      no_color: !ansi::use_color(),
-      xeval_delim,
-      ..Default::default()
-    },
-  );
-
-  ok_buf(serialize_response(
-    base.cmd_id(),
-    &mut builder,
-    msg::BaseArgs {
-      inner_type: msg::Any::StartRes,
-      inner: Some(inner.as_union_value()),
-      ..Default::default()
-    },
-  ))
-}
-
-pub fn op_home_dir(
-  state: &ThreadSafeState,
-  base: &msg::Base<'_>,
-  data: Option<PinnedBuf>,
-) -> CliOpResult {
-  assert!(data.is_none());
-  let cmd_id = base.cmd_id();
+#[derive(Deserialize)]
+struct Dir {
+  kind: String,
+}
 
+pub fn op_dir(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
   state.check_env()?;
-
-  let builder = &mut FlatBufferBuilder::new();
-  let path = dirs::home_dir()
-    .unwrap_or_default()
-    .into_os_string()
-    .into_string()
-    .unwrap_or_default();
-  let path = Some(builder.create_string(&path));
-  let inner = msg::HomeDirRes::create(builder, &msg::HomeDirResArgs { path });
-
-  ok_buf(serialize_response(
-    cmd_id,
-    builder,
-    msg::BaseArgs {
-      inner: Some(inner.as_union_value()),
-      inner_type: msg::Any::HomeDirRes,
-      ..Default::default()
-    },
-  ))
+  let args: Dir = serde_json::from_value(args)?;
+  let path = match args.kind.as_str() {
+    "home" => dirs::home_dir(),
+    "cache" => dirs::cache_dir(),
+    "config" => dirs::config_dir(),
+    "data" => dirs::data_dir(),
+    "dataLocal" => dirs::data_local_dir(),
+    "tmp" => Some(env::temp_dir()),
+    "executable" => dirs::executable_dir(),
+    "audio" => dirs::audio_dir(),
+    "desktop" => dirs::desktop_dir(),
+    "document" => dirs::document_dir(),
+    "download" => dirs::download_dir(),
+    kind => {
+      return Err(ErrBox::from(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("Unknown directory kind: {}", kind),
+      )));
+    }
+  };
+  let path = path.map(|p| p.into_os_string().into_string().unwrap_or_default());
+  Ok(JsonOp::Sync(json!(path)))
 }
 
 pub fn op_exec_path(
@@ -124,18 +85,21 @@ pub fn op_exec_path(
   Ok(JsonOp::Sync(json!(path)))
 }
 
+#[derive(Deserialize)]
+struct SetEnv {
+  key: String,
+  value: String,
+}
+
 pub fn op_set_env(
   state: &ThreadSafeState,
-  base: &msg::Base<'_>,
-  data: Option<PinnedBuf>,
-) -> CliOpResult {
-  assert!(data.is_none());
-  let inner = base.inner_as_set_env().unwrap();
-  let key = inner.key().unwrap();
-  let value = inner.value().unwrap();
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
   state.check_env()?;
-  env::set_var(key, value);
-  ok_buf(empty_buf())
+  let args: SetEnv = serde_json::from_value(args)?;
+  env::set_var(args.key, args.value);
+  Ok(JsonOp::Sync(json!({})))
 }
 
 pub fn op_env(
@@ -148,6 +112,109 @@ pub fn op_env(
   Ok(JsonOp::Sync(json!(v)))
 }
 
+#[derive(Deserialize)]
+struct GetEnv {
+  key: String,
+}
+
+pub fn op_get_env(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let args: GetEnv = serde_json::from_value(args)?;
+  let r = match env::var(&args.key) {
+    Ok(value) => Some(value),
+    Err(env::VarError::NotPresent) => None,
+    Err(err @ env::VarError::NotUnicode(_)) => return Err(ErrBox::from(err)),
+  };
+  Ok(JsonOp::Sync(json!(r)))
+}
+
+#[derive(Deserialize)]
+struct DeleteEnv {
+  key: String,
+}
+
+pub fn op_delete_env(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let args: DeleteEnv = serde_json::from_value(args)?;
+  env::remove_var(args.key);
+  Ok(JsonOp::Sync(json!({})))
+}
+
+pub fn op_os_info(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+
+  let hostname = sys_info::hostname().unwrap_or_default();
+  let release = sys_info::os_release().unwrap_or_default();
+  let cpu_count = num_cpus::get();
+  let (total_memory, free_memory) = sys_info::mem_info()
+    .map(|m| (m.total * 1024, m.free * 1024))
+    .unwrap_or((0, 0));
+
+  // `env::consts::OS` reports "macos", but downstream platform-branching
+  // code (and Node's `os.platform()`) expects "darwin".
+  let os = match env::consts::OS {
+    "macos" => "darwin",
+    os => os,
+  };
+
+  Ok(JsonOp::Sync(json!({
+    "os": os,
+    "release": release,
+    "hostname": hostname,
+    "cpuCount": cpu_count,
+    "totalMemory": total_memory,
+    "freeMemory": free_memory,
+  })))
+}
+
+pub fn op_loadavg(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let loadavg = sys_info::loadavg().unwrap_or(sys_info::LoadAvg {
+    one: 0.0,
+    five: 0.0,
+    fifteen: 0.0,
+  });
+  Ok(JsonOp::Sync(json!([
+    loadavg.one,
+    loadavg.five,
+    loadavg.fifteen
+  ])))
+}
+
+pub fn op_os_uptime(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let uptime = sys_info::boottime()
+    .map(|boottime| {
+      let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+      (now - boottime.tv_sec).max(0)
+    })
+    .unwrap_or(0);
+  Ok(JsonOp::Sync(json!(uptime)))
+}
+
 #[derive(Deserialize)]
 struct Exit {
   code: i32,